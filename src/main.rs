@@ -3,18 +3,54 @@
 extern crate serde_derive;
 
 use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
 use std::collections::HashMap;
 use std::error::Error;
 use eventstore::es6::connection::Connection;
-use eventstore::es6::grpc::event_store::client::shared::Uuid;
+use eventstore::es6::types::{EventData, LinkTos};
 
 const HORIZONTAL_SLOT_COUNT: usize = 7;
 const VERTICAL_SLOT_COUNT: usize = 6;
-const SLOT_COUNT: usize = HORIZONTAL_SLOT_COUNT * VERTICAL_SLOT_COUNT;
+const WIN_LENGTH: usize = 4;
 
 type Column = usize;
 type GameId = usize;
 
+/// The dimensions and win condition of a board. Carrying this per game lets the
+/// same engine play standard Connect Four as well as variants such as Connect-5
+/// or larger grids.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct BoardSpec {
+    width: usize,
+    height: usize,
+    win_len: usize,
+}
+
+impl BoardSpec {
+    pub const fn new(width: usize, height: usize, win_len: usize) -> Self {
+        BoardSpec {
+            width,
+            height,
+            win_len,
+        }
+    }
+
+    /// The classic 7×6 board with a win length of four.
+    pub const fn standard() -> Self {
+        BoardSpec::new(HORIZONTAL_SLOT_COUNT, VERTICAL_SLOT_COUNT, WIN_LENGTH)
+    }
+
+    pub const fn slot_count(&self) -> usize {
+        self.width * self.height
+    }
+}
+
+impl Default for BoardSpec {
+    fn default() -> Self {
+        BoardSpec::standard()
+    }
+}
+
 /*********************************************/
 /*** Events                                  */
 /*********************************************/
@@ -29,6 +65,8 @@ struct GameCreated {
     id: GameId,
     player1: Player,
     player2: Player,
+    spec: BoardSpec,
+    ai: Option<ai::AIPlayer>,
     created: DateTime<Utc>,
 }
 
@@ -52,6 +90,8 @@ enum GameCommands {
 struct CreateGame {
     player1: Player,
     player2: Player,
+    spec: BoardSpec,
+    ai: Option<ai::AIDifficulty>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -65,9 +105,13 @@ struct PlaceToken {
 /*********************************************/
 /*** Validators                              */
 /*********************************************/
-fn is_valid_move(board: &Board, action: &PlaceToken) -> bool {
-    for pos in column_positions(action.column).iter() {
-        let idx = pos.translate();
+fn is_valid_move(spec: &BoardSpec, board: &Board, action: &PlaceToken) -> bool {
+    if action.column >= spec.width {
+        return false;
+    }
+
+    for pos in column_positions(spec, action.column).iter() {
+        let idx = pos.translate(spec);
 
         if let Slot::Empty = board[idx] {
             return true;
@@ -79,7 +123,7 @@ fn is_valid_move(board: &Board, action: &PlaceToken) -> bool {
 
 fn can_create_game(games: &Games, command: &CreateGame) -> bool {
     for game in games.values() {
-        if game.game_over().is_none() && (game.player1.name == command.player1.name
+        if !game.status.is_over() && (game.player1.name == command.player1.name
             || game.player1.name == command.player2.name
             || game.player2.name == command.player1.name
             || game.player2.name == command.player2.name)
@@ -91,52 +135,76 @@ fn can_create_game(games: &Games, command: &CreateGame) -> bool {
     true
 }
 
+/// The four directions a winning line can grow in: right (+x), up (+y), and the
+/// two rising diagonals (+x+y, -x+y). Scanning only forwards from each occupied
+/// slot covers every line exactly once.
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+
 fn check_game_over<'a>(
+    spec: &BoardSpec,
     board: &Board,
     player1: &'a Player,
     player2: &'a Player,
 ) -> Option<&'a Player> {
-    for pos in board_positions().iter() {
-        let slot = board[pos.translate()];
-
-        match slot {
+    for pos in board_positions(spec).iter() {
+        let token = match board[pos.translate(spec)] {
             Slot::Empty => continue,
-            Slot::Occupied(token) => {
-                let on_right_line = pos.x + 3 < HORIZONTAL_SLOT_COUNT
-                    && board[pos.add_x(1).translate()] == slot
-                    && board[pos.add_x(2).translate()] == slot
-                    && board[pos.add_x(3).translate()] == slot;
-
-                let on_top_line = board[pos.add_y(1).translate()] == slot
-                    && board[pos.add_y(2).translate()] == slot
-                    && board[pos.add_y(3).translate()] == slot;
-
-                let on_up_right_line = pos.x + 3 < HORIZONTAL_SLOT_COUNT
-                    && board[pos.add_x(1).add_y(1).translate()] == slot
-                    && board[pos.add_x(2).add_y(2).translate()] == slot
-                    && board[pos.add_x(3).add_y(3).translate()] == slot;
-
-                let on_up_left_line = pos.x >= 3
-                    && board[pos.sub_x(1).add_y(1).translate()] == slot
-                    && board[pos.sub_x(2).add_y(2).translate()] == slot
-                    && board[pos.sub_x(3).add_y(3).translate()] == slot;
-
-                if on_right_line
-                    || (pos.y + 3 < VERTICAL_SLOT_COUNT
-                        && (on_top_line || on_up_right_line || on_up_left_line))
+            Slot::Occupied(token) => token,
+        };
+
+        for (dx, dy) in DIRECTIONS.iter() {
+            let mut count = 1;
+
+            for step in 1..spec.win_len as isize {
+                let x = pos.x as isize + dx * step;
+                let y = pos.y as isize + dy * step;
+
+                if x < 0 || y < 0 || x >= spec.width as isize || y >= spec.height as isize {
+                    break;
+                }
+
+                if board[Position::from_coord(x as usize, y as usize).translate(spec)]
+                    == Slot::Occupied(token)
                 {
-                    if player1.token == token {
-                        return Some(player1);
-                    } else {
-                        return Some(player2);
-                    }
+                    count += 1;
+                } else {
+                    break;
                 }
             }
+
+            if count >= spec.win_len {
+                return Some(if player1.token == token { player1 } else { player2 });
+            }
         }
     }
 
     None
 }
+
+/// The terminal status of a board: a player has a line, the board is full with
+/// no line, or play continues.
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome {
+    Win(Player),
+    Draw,
+    Ongoing,
+}
+
+fn board_full(board: &Board) -> bool {
+    board.iter().all(|slot| *slot != Slot::Empty)
+}
+
+/// Classify a board: a completed line wins, an otherwise full board is a draw,
+/// anything else is still ongoing.
+fn outcome(spec: &BoardSpec, board: &Board, player1: &Player, player2: &Player) -> Outcome {
+    if let Some(player) = check_game_over(spec, board, player1, player2) {
+        Outcome::Win(player.clone())
+    } else if board_full(board) {
+        Outcome::Draw
+    } else {
+        Outcome::Ongoing
+    }
+}
 /*********************************************/
 /*********************************************/
 /*** Projections                             */
@@ -148,7 +216,11 @@ fn project_all_games(games: &mut Games, event: &GameEvents) {
                 id: event.id,
                 player1: event.player1.clone(),
                 player2: event.player2.clone(),
-                board: empty_board(),
+                spec: event.spec,
+                board: empty_board(&event.spec),
+                status: GameStatus::WaitingToStart,
+                ai: event.ai,
+                updated: event.created,
             };
 
             games.insert(event.id, game);
@@ -156,15 +228,27 @@ fn project_all_games(games: &mut Games, event: &GameEvents) {
 
         GameEvents::TokenPlaced(event) => {
             if let Some(game) = games.get_mut(&event.game) {
-                project_board(&mut game.board, event)
+                project_board(&game.spec, &mut game.board, event);
+                game.status = project_game_status(game, event);
+                game.updated = event.created;
             }
         }
     }
 }
 
-fn project_board(boards: &mut Board, event: &TokenPlaced) {
-    for pos in column_positions(event.column).iter() {
-        let idx = pos.translate();
+/// Advance a game's lifecycle after a token was placed: a completed line wins,
+/// a full board draws, otherwise play passes to the other colour.
+fn project_game_status(game: &Game, event: &TokenPlaced) -> GameStatus {
+    match outcome(&game.spec, &game.board, &game.player1, &game.player2) {
+        Outcome::Win(player) => GameStatus::Won(player),
+        Outcome::Draw => GameStatus::Draw,
+        Outcome::Ongoing => GameStatus::Turn(project_next_color_to_play(event.token, event)),
+    }
+}
+
+fn project_board(spec: &BoardSpec, boards: &mut Board, event: &TokenPlaced) {
+    for pos in column_positions(spec, event.column).iter() {
+        let idx = pos.translate(spec);
 
         if let Slot::Empty = boards[idx] {
             boards[idx] = Slot::Occupied(event.token);
@@ -197,55 +281,18 @@ struct Position {
 }
 
 impl Position {
-    pub fn translate(&self) -> usize {
-        self.x + HORIZONTAL_SLOT_COUNT * self.y
+    pub fn translate(&self, spec: &BoardSpec) -> usize {
+        self.x + spec.width * self.y
     }
 
     pub fn from_coord(x: usize, y: usize) -> Self {
         Position { x, y }
     }
 
-    pub fn from_index(idx: usize) -> Self {
-        let mut x = idx;
-        let mut y = 0;
-
-        loop {
-            if x < HORIZONTAL_SLOT_COUNT {
-                break;
-            }
-
-            x -= HORIZONTAL_SLOT_COUNT;
-            y += 1;
-        }
-
-        Position { x, y }
-    }
-
-    pub fn add_x(self, i: usize) -> Self {
-        Position {
-            x: self.x + i,
-            ..self
-        }
-    }
-
-    pub fn sub_x(self, i: usize) -> Self {
-        Position {
-            x: self.x - i,
-            ..self
-        }
-    }
-
-    pub fn add_y(self, i: usize) -> Self {
+    pub fn from_index(idx: usize, spec: &BoardSpec) -> Self {
         Position {
-            y: self.y + i,
-            ..self
-        }
-    }
-
-    pub fn sub_y(self, i: usize) -> Self {
-        Position {
-            y: self.y + i,
-            ..self
+            x: idx % spec.width,
+            y: idx / spec.width,
         }
     }
 }
@@ -271,126 +318,645 @@ impl Slot {
     }
 }
 
-type Board = [Slot; SLOT_COUNT];
+type Board = Vec<Slot>;
 
+#[derive(Clone, Debug, PartialEq)]
 enum GameStatus {
-    Ongoing,
-    Terminated,
+    WaitingToStart,
+    Turn(Token),
+    Won(Player),
+    Draw,
+}
+
+impl GameStatus {
+    pub fn is_over(&self) -> bool {
+        matches!(self, GameStatus::Won(_) | GameStatus::Draw)
+    }
+}
+
+/// Error returned by `command_processing` when a command cannot be applied to
+/// the current game state.
+#[derive(Clone, Debug, PartialEq)]
+enum GameError {
+    UnknownGame,
+    NotYourTurn,
+    GameOver,
+    InvalidMove,
+    PlayerUnavailable,
 }
 
 struct Game {
     id: GameId,
     player1: Player,
     player2: Player,
+    spec: BoardSpec,
     board: Board,
+    status: GameStatus,
+    /// Set when one side is computer-controlled, so the runtime can reply after
+    /// the human moves.
+    ai: Option<ai::AIPlayer>,
+    /// `created` time of the last event folded into this game. Clients use it as
+    /// a polling cursor: a request carrying an equal timestamp gets a `304`.
+    updated: DateTime<Utc>,
 }
 
 impl Game {
     pub fn game_over(&self) -> Option<&Player> {
-        for pos in board_positions().iter() {
-            let slot = self.board[pos.translate()];
-
-            match slot {
-                Slot::Empty => continue,
-                Slot::Occupied(token) => {
-                    let on_right_line = pos.x + 3 < HORIZONTAL_SLOT_COUNT
-                        && self.board[pos.add_x(1).translate()] == slot
-                        && self.board[pos.add_x(2).translate()] == slot
-                        && self.board[pos.add_x(3).translate()] == slot;
-
-                    let on_top_line = self.board[pos.add_y(1).translate()] == slot
-                        && self.board[pos.add_y(2).translate()] == slot
-                        && self.board[pos.add_y(3).translate()] == slot;
-
-                    let on_up_right_line = pos.x + 3 < HORIZONTAL_SLOT_COUNT
-                        && self.board[pos.add_x(1).add_y(1).translate()] == slot
-                        && self.board[pos.add_x(2).add_y(2).translate()] == slot
-                        && self.board[pos.add_x(3).add_y(3).translate()] == slot;
-
-                    let on_up_left_line = pos.x >= 3
-                        && self.board[pos.sub_x(1).add_y(1).translate()] == slot
-                        && self.board[pos.sub_x(2).add_y(2).translate()] == slot
-                        && self.board[pos.sub_x(3).add_y(3).translate()] == slot;
-
-                    if on_right_line
-                        || (pos.y + 3 < VERTICAL_SLOT_COUNT
-                        && (on_top_line || on_up_right_line || on_up_left_line))
+        check_game_over(&self.spec, &self.board, &self.player1, &self.player2)
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        outcome(&self.spec, &self.board, &self.player1, &self.player2)
+    }
+}
+
+/*********************************************/
+/*** AI                                      */
+/*********************************************/
+mod ai {
+    use super::*;
+
+    /// Difficulty levels for a computer opponent, each mapping to a search depth
+    /// for the negamax search.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum AIDifficulty {
+        Easy,
+        Medium,
+        Hard,
+    }
+
+    impl AIDifficulty {
+        pub fn depth(self) -> usize {
+            match self {
+                AIDifficulty::Easy => 3,
+                AIDifficulty::Medium => 5,
+                AIDifficulty::Hard => 7,
+            }
+        }
+    }
+
+    /// Designates one side of a game as computer-controlled: which token the AI
+    /// plays and how deeply it searches.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct AIPlayer {
+        pub token: Token,
+        pub difficulty: AIDifficulty,
+    }
+
+    /// Center-first column ordering for a board of the given width. Exploring
+    /// the middle columns first surfaces the strongest replies early, which
+    /// tightens the alpha-beta window and prunes more branches.
+    fn column_order(spec: &BoardSpec) -> Vec<Column> {
+        let center = spec.width / 2;
+        let mut order = Vec::with_capacity(spec.width);
+        order.push(center);
+
+        for offset in 1..=center {
+            if center >= offset {
+                order.push(center - offset);
+            }
+            if center + offset < spec.width {
+                order.push(center + offset);
+            }
+        }
+
+        order
+    }
+
+    fn opponent(token: Token) -> Token {
+        match token {
+            Token::Red => Token::Yellow,
+            Token::Yellow => Token::Red,
+        }
+    }
+
+    fn is_legal(spec: &BoardSpec, board: &Board, column: Column) -> bool {
+        column_positions(spec, column)
+            .iter()
+            .any(|pos| board[pos.translate(spec)] == Slot::Empty)
+    }
+
+    fn drop_token(spec: &BoardSpec, board: &mut Board, column: Column, token: Token) {
+        project_board(
+            spec,
+            board,
+            &TokenPlaced {
+                game: 0,
+                token,
+                column,
+                created: Utc::now(),
+            },
+        );
+    }
+
+    fn winner(spec: &BoardSpec, board: &Board) -> Option<Token> {
+        let red = Player {
+            name: String::new(),
+            token: Token::Red,
+        };
+        let yellow = Player {
+            name: String::new(),
+            token: Token::Yellow,
+        };
+
+        check_game_over(spec, board, &red, &yellow).map(|player| player.token)
+    }
+
+    /// Heuristic for non-terminal leaves: reward the mover's open windows that
+    /// are one short of a line (5 points) or two short (2 points) and penalise
+    /// the opponent's by the same amounts.
+    fn heuristic(spec: &BoardSpec, board: &Board, token: Token) -> i32 {
+        let mut score = 0;
+
+        for pos in board_positions(spec).iter() {
+            for (dx, dy) in DIRECTIONS.iter() {
+                let mut mine = 0;
+                let mut theirs = 0;
+                let mut inside = true;
+
+                for step in 0..spec.win_len as isize {
+                    let x = pos.x as isize + dx * step;
+                    let y = pos.y as isize + dy * step;
+
+                    if x < 0
+                        || y < 0
+                        || x >= spec.width as isize
+                        || y >= spec.height as isize
                     {
-                        if self.player1.token == token {
-                            return Some(&self.player1);
-                        } else {
-                            return Some(&self.player2);
-                        }
+                        inside = false;
+                        break;
+                    }
+
+                    match board[Position::from_coord(x as usize, y as usize).translate(spec)] {
+                        Slot::Empty => {}
+                        Slot::Occupied(t) if t == token => mine += 1,
+                        Slot::Occupied(_) => theirs += 1,
                     }
                 }
+
+                if !inside {
+                    continue;
+                }
+
+                let need = spec.win_len as i32;
+                if theirs == 0 && mine == need - 1 {
+                    score += 5;
+                } else if theirs == 0 && mine == need - 2 {
+                    score += 2;
+                } else if mine == 0 && theirs == need - 1 {
+                    score -= 5;
+                } else if mine == 0 && theirs == need - 2 {
+                    score -= 2;
+                }
             }
         }
 
-        None
+        score
+    }
+
+    fn negamax(
+        spec: &BoardSpec,
+        order: &[Column],
+        board: &Board,
+        token: Token,
+        depth: usize,
+        mut alpha: i32,
+        beta: i32,
+        moves_played: i32,
+    ) -> i32 {
+        // A line already exists, so the side to move has just been beaten.
+        if winner(spec, board).is_some() {
+            return -(spec.slot_count() as i32 + 1 - moves_played);
+        }
+
+        if board_full(board) {
+            return 0;
+        }
+
+        if depth == 0 {
+            return heuristic(spec, board, token);
+        }
+
+        let mut value = i32::MIN + 1;
+
+        for &column in order.iter() {
+            if !is_legal(spec, board, column) {
+                continue;
+            }
+
+            let mut next = board.clone();
+            drop_token(spec, &mut next, column, token);
+
+            let score = -negamax(
+                spec,
+                order,
+                &next,
+                opponent(token),
+                depth - 1,
+                -beta,
+                -alpha,
+                moves_played + 1,
+            );
+
+            value = value.max(score);
+            alpha = alpha.max(value);
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        value
+    }
+
+    /// Pick the best column for `token` on `board`, searching `depth` plies with
+    /// negamax and alpha-beta pruning. Returns `None` when no legal move remains.
+    pub fn best_move(spec: &BoardSpec, board: &Board, token: Token, depth: usize) -> Option<Column> {
+        let order = column_order(spec);
+        let mut best: Option<(Column, i32)> = None;
+        let mut alpha = i32::MIN + 1;
+        let beta = i32::MAX - 1;
+
+        for &column in order.iter() {
+            if !is_legal(spec, board, column) {
+                continue;
+            }
+
+            let mut next = board.clone();
+            drop_token(spec, &mut next, column, token);
+
+            let score = -negamax(
+                spec,
+                &order,
+                &next,
+                opponent(token),
+                depth.saturating_sub(1),
+                -beta,
+                -alpha,
+                1,
+            );
+
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((column, score));
+            }
+
+            alpha = alpha.max(score);
+        }
+
+        best.map(|(column, _)| column)
     }
 }
+/*********************************************/
 
 type GameStatues = HashMap<GameId, GameStatus>;
 type Boards = HashMap<GameId, Board>;
 type Games = HashMap<GameId, Game>;
 
-const fn empty_board() -> Board {
-    [Slot::Empty; SLOT_COUNT]
+fn empty_board(spec: &BoardSpec) -> Board {
+    vec![Slot::Empty; spec.slot_count()]
 }
 
-fn board_positions() -> [Position; SLOT_COUNT] {
-    let init_pos = Position { x: 0, y: 0 };
+fn board_positions(spec: &BoardSpec) -> Vec<Position> {
+    let mut positions = Vec::with_capacity(spec.slot_count());
 
-    let mut positions = [init_pos; SLOT_COUNT];
+    for y in 0..spec.height {
+        for x in 0..spec.width {
+            positions.push(Position { x, y });
+        }
+    }
 
-    for x in 0..HORIZONTAL_SLOT_COUNT {
-        for y in 0..VERTICAL_SLOT_COUNT {
-            let pos = Position { x, y };
+    positions
+}
 
-            positions[pos.translate()] = pos;
+fn column_positions(spec: &BoardSpec, x: Column) -> Vec<Position> {
+    (0..spec.height).map(|y| Position { x, y }).collect()
+}
+
+/*********************************************/
+/*** Move records                            */
+/*********************************************/
+mod record {
+    use super::*;
+
+    /// Why a textual game record could not be replayed.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ReplayError {
+        Malformed,
+        IllegalMove { column: Column },
+        MoveAfterWin,
+    }
+
+    /// Percent-escape the field separator (and the escape marker itself) so a
+    /// player name containing `;` cannot shift the surrounding fields.
+    fn escape(field: &str) -> String {
+        field.replace('%', "%25").replace(';', "%3B")
+    }
+
+    fn unescape(field: &str) -> String {
+        field.replace("%3B", ";").replace("%25", "%")
+    }
+
+    fn token_code(token: Token) -> char {
+        match token {
+            Token::Red => 'R',
+            Token::Yellow => 'Y',
         }
     }
 
-    positions
+    fn parse_token(code: &str) -> Result<Token, ReplayError> {
+        match code {
+            "R" => Ok(Token::Red),
+            "Y" => Ok(Token::Yellow),
+            _ => Err(ReplayError::Malformed),
+        }
+    }
+
+    /// Encode a game's history as a compact, portable record: a header carrying
+    /// the board spec, both players with their tokens and the game's timestamp,
+    /// followed by the ordered `token;column` pairs that were dropped.
+    pub fn encode_game(events: &[GameEvents]) -> String {
+        let mut fields: Vec<String> = Vec::new();
+
+        for event in events {
+            match event {
+                GameEvents::GameCreated(created) => {
+                    fields.push("C4".to_string());
+                    fields.push(created.spec.width.to_string());
+                    fields.push(created.spec.height.to_string());
+                    fields.push(created.spec.win_len.to_string());
+                    fields.push(escape(&created.player1.name));
+                    fields.push(token_code(created.player1.token).to_string());
+                    fields.push(escape(&created.player2.name));
+                    fields.push(token_code(created.player2.token).to_string());
+                    fields.push(created.created.to_rfc3339());
+                }
+
+                GameEvents::TokenPlaced(placed) => {
+                    fields.push(token_code(placed.token).to_string());
+                    fields.push(placed.column.to_string());
+                }
+            }
+        }
+
+        fields.join(";")
+    }
+
+    /// Parse and re-derive a game from a record produced by `encode_game`,
+    /// validating that every move was legal and that no move follows a win.
+    pub fn replay(record: &str) -> Result<Game, ReplayError> {
+        let fields: Vec<&str> = record.split(';').collect();
+
+        if fields.len() < 9 || fields[0] != "C4" {
+            return Err(ReplayError::Malformed);
+        }
+
+        let parse_usize = |raw: &str| raw.parse::<usize>().map_err(|_| ReplayError::Malformed);
+
+        let spec = BoardSpec::new(
+            parse_usize(fields[1])?,
+            parse_usize(fields[2])?,
+            parse_usize(fields[3])?,
+        );
+
+        let player1 = Player {
+            name: unescape(fields[4]),
+            token: parse_token(fields[5])?,
+        };
+        let player2 = Player {
+            name: unescape(fields[6]),
+            token: parse_token(fields[7])?,
+        };
+
+        let created = DateTime::parse_from_rfc3339(fields[8])
+            .map_err(|_| ReplayError::Malformed)?
+            .with_timezone(&Utc);
+
+        let moves = &fields[9..];
+        if moves.len() % 2 != 0 {
+            return Err(ReplayError::Malformed);
+        }
+
+        let player_for = |token: Token| {
+            if player1.token == token {
+                player1.clone()
+            } else {
+                player2.clone()
+            }
+        };
+
+        let mut board = empty_board(&spec);
+        let mut winner: Option<Player> = None;
+        let mut last_token = None;
+
+        for pair in moves.chunks(2) {
+            if winner.is_some() {
+                return Err(ReplayError::MoveAfterWin);
+            }
+
+            let token = parse_token(pair[0])?;
+            let column = parse_usize(pair[1])?;
+
+            let action = PlaceToken {
+                game: 0,
+                player: player_for(token),
+                column,
+            };
+
+            if !is_valid_move(&spec, &board, &action) {
+                return Err(ReplayError::IllegalMove { column });
+            }
+
+            project_board(
+                &spec,
+                &mut board,
+                &TokenPlaced {
+                    game: 0,
+                    token,
+                    column,
+                    created,
+                },
+            );
+
+            last_token = Some(token);
+
+            if let Some(player) = check_game_over(&spec, &board, &player1, &player2) {
+                winner = Some(player.clone());
+            }
+        }
+
+        let status = if let Some(player) = winner {
+            GameStatus::Won(player)
+        } else if board.iter().all(|slot| *slot != Slot::Empty) {
+            GameStatus::Draw
+        } else {
+            match last_token {
+                Some(Token::Red) => GameStatus::Turn(Token::Yellow),
+                Some(Token::Yellow) => GameStatus::Turn(Token::Red),
+                None => GameStatus::WaitingToStart,
+            }
+        };
+
+        Ok(Game {
+            id: 0,
+            player1,
+            player2,
+            spec,
+            board,
+            status,
+            ai: None,
+            updated: created,
+        })
+    }
+}
+/*********************************************/
+
+#[test]
+fn test_full_board_without_line_is_a_draw() {
+    // A 2×2 board can never hold a line of four, so filling it must draw.
+    let spec = BoardSpec::new(2, 2, 4);
+    let player1 = Player {
+        token: Token::Red,
+        name: "1".to_string(),
+    };
+    let player2 = Player {
+        token: Token::Yellow,
+        name: "2".to_string(),
+    };
+
+    let board = vec![
+        Slot::Occupied(Token::Red),
+        Slot::Occupied(Token::Yellow),
+        Slot::Occupied(Token::Yellow),
+        Slot::Occupied(Token::Red),
+    ];
+
+    debug_assert!(board_full(&board));
+    debug_assert_eq!(Outcome::Draw, outcome(&spec, &board, &player1, &player2));
 }
 
-fn column_positions(x: Column) -> [Position; VERTICAL_SLOT_COUNT] {
-    let mut indexes = [Position { x: 0, y: 0 }; VERTICAL_SLOT_COUNT];
+#[test]
+fn test_record_round_trips_a_game() {
+    let spec = BoardSpec::standard();
+    let player1 = Player {
+        token: Token::Red,
+        name: "red".to_string(),
+    };
+    let player2 = Player {
+        token: Token::Yellow,
+        name: "yellow".to_string(),
+    };
+
+    let created = Utc::now();
+    let mut events = vec![GameEvents::GameCreated(GameCreated {
+        id: 0,
+        player1: player1.clone(),
+        player2: player2.clone(),
+        spec,
+        ai: None,
+        created,
+    })];
 
-    for y in 0..VERTICAL_SLOT_COUNT {
-        indexes[y] = Position { x, y };
+    for column in 0..4 {
+        events.push(GameEvents::TokenPlaced(TokenPlaced {
+            game: 0,
+            token: Token::Red,
+            column,
+            created,
+        }));
+
+        if column != 3 {
+            events.push(GameEvents::TokenPlaced(TokenPlaced {
+                game: 0,
+                token: Token::Yellow,
+                column,
+                created,
+            }));
+        }
     }
 
-    indexes
+    let encoded = record::encode_game(&events);
+    let game = record::replay(&encoded).expect("record replays");
+
+    debug_assert_eq!(GameStatus::Won(player1), game.status);
+}
+
+#[test]
+fn test_record_preserves_names_with_separators() {
+    let spec = BoardSpec::standard();
+    let player1 = Player {
+        token: Token::Red,
+        name: "a;b%c".to_string(),
+    };
+    let player2 = Player {
+        token: Token::Yellow,
+        name: "plain".to_string(),
+    };
+
+    let created = Utc::now();
+    let events = vec![GameEvents::GameCreated(GameCreated {
+        id: 0,
+        player1: player1.clone(),
+        player2: player2.clone(),
+        spec,
+        ai: None,
+        created,
+    })];
+
+    let game = record::replay(&record::encode_game(&events)).expect("record replays");
+
+    debug_assert_eq!(player1, game.player1);
+    debug_assert_eq!(player2, game.player2);
+}
+
+#[test]
+fn test_record_rejects_move_after_win() {
+    let spec = BoardSpec::standard();
+    let created = Utc::now();
+    let header = format!(
+        "C4;{};{};{};red;R;yellow;Y;{}",
+        spec.width,
+        spec.height,
+        spec.win_len,
+        created.to_rfc3339()
+    );
+
+    // Red completes a vertical line in column 0, then tries to keep playing.
+    let record = format!("{};R;0;R;0;R;0;R;0;R;1", header);
+
+    debug_assert_eq!(Err(record::ReplayError::MoveAfterWin), record::replay(&record));
 }
 
 #[test]
 fn test_position_translation() {
-    for pos in board_positions().iter() {
-        debug_assert_eq!(pos, &Position::from_index(pos.translate()));
+    let spec = BoardSpec::standard();
+    for pos in board_positions(&spec).iter() {
+        debug_assert_eq!(pos, &Position::from_index(pos.translate(&spec), &spec));
     }
 }
 
 #[test]
 fn test_check_position_translate_idx() {
-    let mut board = empty_board();
-    for pos in board_positions().iter() {
-        board[pos.translate()] = Slot::Occupied(Token::Red);
+    let spec = BoardSpec::standard();
+    let mut board = empty_board(&spec);
+    for pos in board_positions(&spec).iter() {
+        board[pos.translate(&spec)] = Slot::Occupied(Token::Red);
     }
 }
 
 #[test]
 fn test_check_column_position_translate_idx() {
-    let mut board = empty_board();
-    for column in 0..HORIZONTAL_SLOT_COUNT {
-        for pos in column_positions(column).iter() {
-            board[pos.translate()] = Slot::Occupied(Token::Red);
+    let spec = BoardSpec::standard();
+    let mut board = empty_board(&spec);
+    for column in 0..spec.width {
+        for pos in column_positions(&spec, column).iter() {
+            board[pos.translate(&spec)] = Slot::Occupied(Token::Red);
         }
     }
 }
 
 #[test]
 fn test_no_winner_empty_board() {
+    let spec = BoardSpec::standard();
     let player1 = Player {
         token: Token::Red,
         name: "1".to_string(),
@@ -401,7 +967,7 @@ fn test_no_winner_empty_board() {
         name: "2".to_string(),
     };
 
-    debug_assert_eq!(None, check_game_over(&empty_board(), &player1, &player2));
+    debug_assert_eq!(None, check_game_over(&spec, &empty_board(&spec), &player1, &player2));
 }
 
 #[test]
@@ -427,7 +993,8 @@ fn test_detect_win_condition_horizontal() {
         }
     }
 
-    let mut board = empty_board();
+    let spec = BoardSpec::standard();
+    let mut board = empty_board(&spec);
     let player1 = Player {
         token: Token::Red,
         name: "1".to_string(),
@@ -439,10 +1006,10 @@ fn test_detect_win_condition_horizontal() {
     };
 
     for event in events.iter() {
-        project_board(&mut board, event);
+        project_board(&spec, &mut board, event);
     }
 
-    debug_assert_eq!(Some(&player1), check_game_over(&board, &player1, &player2));
+    debug_assert_eq!(Some(&player1), check_game_over(&spec, &board, &player1, &player2));
 }
 
 #[test]
@@ -468,7 +1035,8 @@ fn test_detect_win_condition_vertical() {
         }
     }
 
-    let mut board = empty_board();
+    let spec = BoardSpec::standard();
+    let mut board = empty_board(&spec);
     let player1 = Player {
         token: Token::Red,
         name: "1".to_string(),
@@ -480,41 +1048,136 @@ fn test_detect_win_condition_vertical() {
     };
 
     for event in events.iter() {
-        project_board(&mut board, event);
+        project_board(&spec, &mut board, event);
     }
 
-    debug_assert_eq!(Some(&player1), check_game_over(&board, &player1, &player2));
+    debug_assert_eq!(Some(&player1), check_game_over(&spec, &board, &player1, &player2));
 }
 
-fn command_processing(games: &Games, cmd: GameCommands) -> Option<GameEvents> {
+#[test]
+fn test_connect_five_needs_five_in_a_row() {
+    let spec = BoardSpec::new(9, 7, 5);
+    let mut board = empty_board(&spec);
+
+    let player1 = Player {
+        token: Token::Red,
+        name: "1".to_string(),
+    };
+
+    let player2 = Player {
+        token: Token::Yellow,
+        name: "2".to_string(),
+    };
+
+    for column in 0..4 {
+        board[Position::from_coord(column, 0).translate(&spec)] = Slot::Occupied(Token::Red);
+    }
+    debug_assert_eq!(None, check_game_over(&spec, &board, &player1, &player2));
+
+    board[Position::from_coord(4, 0).translate(&spec)] = Slot::Occupied(Token::Red);
+    debug_assert_eq!(Some(&player1), check_game_over(&spec, &board, &player1, &player2));
+}
+
+#[test]
+fn test_ai_takes_the_winning_move() {
+    let spec = BoardSpec::standard();
+    let mut board = empty_board(&spec);
+
+    for column in 0..3 {
+        board[Position::from_coord(column, 0).translate(&spec)] = Slot::Occupied(Token::Red);
+    }
+
+    debug_assert_eq!(Some(3), ai::best_move(&spec, &board, Token::Red, 4));
+}
+
+#[test]
+fn test_reject_move_out_of_turn() {
+    let player1 = Player {
+        token: Token::Red,
+        name: "1".to_string(),
+    };
+
+    let player2 = Player {
+        token: Token::Yellow,
+        name: "2".to_string(),
+    };
+
+    let mut games: Games = HashMap::new();
+    event_processing(
+        &mut games,
+        &GameEvents::GameCreated(GameCreated {
+            id: 0,
+            player1: player1.clone(),
+            player2: player2.clone(),
+            spec: BoardSpec::standard(),
+            ai: None,
+            created: Utc::now(),
+        }),
+    );
+
+    let red_move = |column| {
+        GameCommands::PlaceToken(PlaceToken {
+            game: 0,
+            player: player1.clone(),
+            column,
+        })
+    };
+
+    let event = command_processing(&games, red_move(0)).expect("red opens");
+    event_processing(&mut games, &event);
+
+    debug_assert_eq!(Err(GameError::NotYourTurn), command_processing(&games, red_move(1)));
+}
+
+fn command_processing(games: &Games, cmd: GameCommands) -> Result<GameEvents, GameError> {
     match cmd {
         GameCommands::CreateGame(params) => {
-            if can_create_game(games, &params) {
-                let id = games.len();
-                return Some(GameEvents::GameCreated(GameCreated {
-                    id,
-                    player1: params.player1.clone(),
-                    player2: params.player2.clone(),
-                    created: Utc::now(),
-                }));
+            if !can_create_game(games, &params) {
+                return Err(GameError::PlayerUnavailable);
             }
+
+            let id = games.len();
+            // A single-player game designates player two as the AI opponent.
+            let ai = params.ai.map(|difficulty| ai::AIPlayer {
+                token: params.player2.token,
+                difficulty,
+            });
+
+            Ok(GameEvents::GameCreated(GameCreated {
+                id,
+                player1: params.player1.clone(),
+                player2: params.player2.clone(),
+                spec: params.spec,
+                ai,
+                created: Utc::now(),
+            }))
         }
 
         GameCommands::PlaceToken(params) => {
-            if let Some(game) = games.get(&params.game) {
-                if is_valid_move(&game.board, &params) {
-                    return Some(GameEvents::TokenPlaced(TokenPlaced {
-                        game: params.game,
-                        token: params.player.token,
-                        column: params.column,
-                        created: Utc::now(),
-                    }));
-                }
+            let game = games.get(&params.game).ok_or(GameError::UnknownGame)?;
+
+            let expected = match &game.status {
+                GameStatus::WaitingToStart => game.player1.token,
+                GameStatus::Turn(token) => *token,
+                GameStatus::Won(_) | GameStatus::Draw => return Err(GameError::GameOver),
+            };
+
+            if params.player.token != expected {
+                return Err(GameError::NotYourTurn);
+            }
+
+            if !is_valid_move(&game.spec, &game.board, &params) {
+                return Err(GameError::InvalidMove);
             }
+
+            Ok(GameEvents::TokenPlaced(TokenPlaced {
+                game: params.game,
+                token: params.player.token,
+                column: params.column,
+                created: Utc::now(),
+            }))
         }
     }
-
-    None
 }
 
 fn event_processing(games: &mut Games, event: &GameEvents) {
@@ -524,7 +1187,11 @@ fn event_processing(games: &mut Games, event: &GameEvents) {
                 id: params.id,
                 player1: params.player1.clone(),
                 player2: params.player2.clone(),
-                board: empty_board(),
+                spec: params.spec,
+                board: empty_board(&params.spec),
+                status: GameStatus::WaitingToStart,
+                ai: params.ai,
+                updated: params.created,
             };
 
             games.insert(params.id, game);
@@ -532,69 +1199,344 @@ fn event_processing(games: &mut Games, event: &GameEvents) {
 
         GameEvents::TokenPlaced(params) => {
             if let Some(game) = games.get_mut(&params.game) {
-                project_board(&mut game.board, params);
+                project_board(&game.spec, &mut game.board, params);
+                game.status = project_game_status(game, params);
+                game.updated = params.created;
             }
         }
     }
 }
 
-// fn game_loop() {
-//     let mut games: Games = HashMap::new();
-//     loop {
-//         let cmd = wait_for_game_command();
+fn event_type(event: &GameEvents) -> &'static str {
+    match event {
+        GameEvents::GameCreated(_) => "game-created",
+        GameEvents::TokenPlaced(_) => "token-placed",
+    }
+}
 
-//         if let Some(event) = command_processing(&games, cmd) {
-//             event_processing(&mut games, &event);
-//             persist_event(&event);
+fn event_game_id(event: &GameEvents) -> GameId {
+    match event {
+        GameEvents::GameCreated(params) => params.id,
+        GameEvents::TokenPlaced(params) => params.game,
+    }
+}
 
-//             if let GameEvents::TokenPlaced(event) = event {
-//                 let game = games.get(&event.game).expect("Guaranteed the game exists");
+/// Append an event durably to its per-game stream (`game-<id>`). The EventStore
+/// category projection (`$ce-game`) links every one of these streams together so
+/// the whole history can be enumerated on startup.
+async fn persist_event(connection: &Connection, event: &GameEvents) -> Result<(), Box<dyn Error>> {
+    let stream = format!("game-{}", event_game_id(event));
+    let data = EventData::json(event_type(event), event)?;
 
-//                 draw_board(&game.board);
+    connection
+        .write_events(stream)
+        .push_event(data)
+        .execute()
+        .await?;
 
-//                 if let Some(player) = game.is_over() {
-//                     notify_winner(player);
+    Ok(())
+}
 
-//                     break;
-//                 }
-//             }
-//         }
-//     }
-// }
+/// Rebuild the in-memory `Games` projection by reading the `$ce-game` category
+/// stream from the beginning and folding every event through `project_all_games`.
+/// Resolving the link events lets a single pass replay all per-game streams, so a
+/// restart reconstructs identical boards before any new command is accepted.
+async fn load_game_events(connection: &Connection) -> Result<Games, Box<dyn Error>> {
+    let mut games: Games = HashMap::new();
 
-// struct State;
+    let mut events = connection
+        .read_stream("$ce-game")
+        .resolve_link_tos(LinkTos::ResolveLink)
+        .start_from_beginning()
+        .iterate_over();
 
-// async fn load_game_events(connection: &Connection) {
-//     connection.read_stream("$ce-games".to_string())
-//         .start_from_beginning();
-// }
+    while let Some(event) = events.try_next().await? {
+        let original = event.get_original_event();
+        let game_event = original.as_json::<GameEvents>()?;
 
-// struct ItemAdded {
-//     id: Uuid,
-//     name: String,
-// }
+        project_all_games(&mut games, &game_event);
+    }
 
-// fn draw_board(board: &Board) {
+    Ok(games)
+}
+
+/*********************************************/
+/*** HTTP API                                */
+/*********************************************/
+mod web {
+    use super::*;
+    use std::sync::Arc;
+
+    use axum::extract::{Path, Query, State};
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use tokio::sync::Mutex;
+
+    /// Shared runtime state. The `Games` projection lives behind an async lock so
+    /// concurrent requests serialise their command processing, and every applied
+    /// event is also appended durably through the EventStore connection.
+    #[derive(Clone)]
+    pub struct AppState {
+        connection: Arc<Connection>,
+        games: Arc<Mutex<Games>>,
+    }
+
+    impl AppState {
+        pub fn new(connection: Connection, games: Games) -> Self {
+            AppState {
+                connection: Arc::new(connection),
+                games: Arc::new(Mutex::new(games)),
+            }
+        }
+    }
+
+    pub fn router(state: AppState) -> Router {
+        Router::new()
+            .route("/games", post(create_game))
+            .route("/games/:id", get(get_game))
+            .route("/games/:id/moves", post(place_token))
+            .with_state(state)
+    }
+
+    #[derive(Deserialize)]
+    struct CreateGameRequest {
+        player1: Player,
+        player2: Player,
+        #[serde(default)]
+        spec: BoardSpec,
+        #[serde(default)]
+        ai: Option<ai::AIDifficulty>,
+    }
+
+    #[derive(Deserialize)]
+    struct PlaceTokenRequest {
+        player: Player,
+        column: usize,
+    }
+
+    #[derive(Deserialize)]
+    struct PollQuery {
+        since: Option<DateTime<Utc>>,
+    }
+
+    /// The full state a client re-renders from. `date_updated` is the polling
+    /// cursor echoed back on every response.
+    #[derive(Serialize)]
+    struct GameSnapshot {
+        id: GameId,
+        date_updated: DateTime<Utc>,
+        spec: BoardSpec,
+        board: Vec<Option<Token>>,
+        turn: Option<Token>,
+        winner: Option<Player>,
+        draw: bool,
+    }
+
+    impl GameSnapshot {
+        fn of(game: &Game) -> Self {
+            let turn = match &game.status {
+                GameStatus::WaitingToStart => Some(game.player1.token),
+                GameStatus::Turn(token) => Some(*token),
+                GameStatus::Won(_) | GameStatus::Draw => None,
+            };
+
+            let winner = match &game.status {
+                GameStatus::Won(player) => Some(player.clone()),
+                _ => None,
+            };
 
-// }
+            GameSnapshot {
+                id: game.id,
+                date_updated: game.updated,
+                spec: game.spec,
+                board: game.board.iter().map(|slot| slot.token()).collect(),
+                turn,
+                winner,
+                draw: game.status == GameStatus::Draw,
+            }
+        }
+    }
+
+    fn status_for(error: &GameError) -> StatusCode {
+        match error {
+            GameError::UnknownGame => StatusCode::NOT_FOUND,
+            GameError::NotYourTurn
+            | GameError::GameOver
+            | GameError::InvalidMove
+            | GameError::PlayerUnavailable => StatusCode::CONFLICT,
+        }
+    }
+
+    /// Validate a command against the shared state, append the resulting event
+    /// durably and only then apply it in memory, returning the affected game id.
+    /// Persisting first keeps the projection from diverging from the store when a
+    /// durable append fails.
+    async fn apply(state: &AppState, cmd: GameCommands) -> Result<GameId, Response> {
+        let mut games = state.games.lock().await;
+
+        let event = command_processing(&games, cmd)
+            .map_err(|error| (status_for(&error), format!("{:?}", error)).into_response())?;
+
+        let id = persist(state, &mut games, event).await?;
+
+        play_ai_moves(state, &mut games, id).await?;
+
+        Ok(id)
+    }
+
+    /// In a single-player game, keep generating and applying the AI opponent's
+    /// moves for as long as it is the AI's turn, routing each reply through the
+    /// same command/event path as a human move.
+    async fn play_ai_moves(
+        state: &AppState,
+        games: &mut Games,
+        id: GameId,
+    ) -> Result<(), Response> {
+        loop {
+            let cmd = {
+                let game = match games.get(&id) {
+                    Some(game) => game,
+                    None => return Ok(()),
+                };
+
+                let ai = match game.ai {
+                    Some(ai) => ai,
+                    None => return Ok(()),
+                };
+
+                if game.status != GameStatus::Turn(ai.token) {
+                    return Ok(());
+                }
+
+                let column =
+                    match ai::best_move(&game.spec, &game.board, ai.token, ai.difficulty.depth()) {
+                        Some(column) => column,
+                        None => return Ok(()),
+                    };
+
+                let player = if game.player1.token == ai.token {
+                    game.player1.clone()
+                } else {
+                    game.player2.clone()
+                };
+
+                GameCommands::PlaceToken(PlaceToken {
+                    game: id,
+                    player,
+                    column,
+                })
+            };
+
+            let event = command_processing(games, cmd)
+                .map_err(|error| (status_for(&error), format!("{:?}", error)).into_response())?;
+
+            persist(state, games, event).await?;
+        }
+    }
+
+    /// Append `event` to the store, then fold it into the in-memory projection.
+    /// Returns the affected game id.
+    async fn persist(
+        state: &AppState,
+        games: &mut Games,
+        event: GameEvents,
+    ) -> Result<GameId, Response> {
+        persist_event(state.connection.as_ref(), &event)
+            .await
+            .map_err(|error| {
+                (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+            })?;
+
+        event_processing(games, &event);
+
+        Ok(event_game_id(&event))
+    }
+
+    async fn snapshot(state: &AppState, id: GameId) -> Response {
+        let games = state.games.lock().await;
+
+        match games.get(&id) {
+            Some(game) => Json(GameSnapshot::of(game)).into_response(),
+            None => (StatusCode::NOT_FOUND, "unknown game").into_response(),
+        }
+    }
+
+    async fn create_game(
+        State(state): State<AppState>,
+        Json(req): Json<CreateGameRequest>,
+    ) -> Response {
+        let cmd = GameCommands::CreateGame(CreateGame {
+            player1: req.player1,
+            player2: req.player2,
+            spec: req.spec,
+            ai: req.ai,
+        });
+
+        match apply(&state, cmd).await {
+            Ok(id) => snapshot(&state, id).await,
+            Err(response) => response,
+        }
+    }
+
+    async fn place_token(
+        State(state): State<AppState>,
+        Path(id): Path<GameId>,
+        Json(req): Json<PlaceTokenRequest>,
+    ) -> Response {
+        let cmd = GameCommands::PlaceToken(PlaceToken {
+            game: id,
+            player: req.player,
+            column: req.column,
+        });
+
+        match apply(&state, cmd).await {
+            Ok(id) => snapshot(&state, id).await,
+            Err(response) => response,
+        }
+    }
+
+    /// Return the current snapshot, or `304 Not Modified` when the client already
+    /// holds the latest `date_updated`.
+    async fn get_game(
+        State(state): State<AppState>,
+        Path(id): Path<GameId>,
+        Query(query): Query<PollQuery>,
+    ) -> Response {
+        let games = state.games.lock().await;
+
+        let game = match games.get(&id) {
+            Some(game) => game,
+            None => return (StatusCode::NOT_FOUND, "unknown game").into_response(),
+        };
+
+        if let Some(since) = query.since {
+            if since >= game.updated {
+                return StatusCode::NOT_MODIFIED.into_response();
+            }
+        }
+
+        Json(GameSnapshot::of(game)).into_response()
+    }
+}
+/*********************************************/
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    // let uri = format!("https://localhost:2113/").parse()?;
-    // let connection = eventstore::es6::connection::Connection::builder()
-    //     .with_default_user(eventstore::Credentials::new("admin", "changeit"))
-    //     .disable_server_certificate_validation()
-    //     .single_node_connection(uri)
-    //     .await?;
+    let uri = "https://localhost:2113/".parse()?;
+    let connection = eventstore::es6::connection::Connection::builder()
+        .with_default_user(eventstore::Credentials::new("admin", "changeit"))
+        .disable_server_certificate_validation()
+        .single_node_connection(uri)
+        .await?;
 
-    // let _board: Board = empty_board();
-    // let _events: Vec<TokenPlaced> = Vec::new();
+    let games = load_game_events(&connection).await?;
 
-    // for pos in board_positions().iter() {
-    //     println!("({}, {})", pos.x, pos.y);
-    // }
+    let state = web::AppState::new(connection, games);
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
 
-    println!("Hello, world!");
+    axum::serve(listener, web::router(state)).await?;
 
     Ok(())
 }